@@ -0,0 +1,170 @@
+//! Per-generation naming configuration.
+//!
+//! [`IdentName`]'s `struct_ident`/`field_ident`/etc. hard-code a fixed
+//! UpperCamel/snake/SHOUTY scheme. [`NamingConfig`] lets a generation
+//! override the [`CaseStyle`] used for each of struct/enum/field/variant/
+//! const idents, so generated code can match the casing conventions of the
+//! source IDL or of an existing hand-written API, while falling back to
+//! pilota's usual defaults when left unset. Every ident produced through it
+//! is run through the [`NamingValidator`] naming-convention pass, so codegen
+//! gets a full report of the renames it applied for free.
+
+use faststr::FastStr;
+
+use crate::{
+    symbol::{CaseStyle, DefId, FileId},
+    validate::{IdentRole, NamingDiagnostic, NamingValidator},
+};
+
+/// Per-generation override of the identifier casing pilota applies. Every
+/// style field defaults to pilota's historical behavior (`None`).
+#[derive(Debug, Default)]
+pub struct NamingConfig {
+    pub struct_style: Option<CaseStyle>,
+    pub enum_style: Option<CaseStyle>,
+    pub field_style: Option<CaseStyle>,
+    pub variant_style: Option<CaseStyle>,
+    pub const_style: Option<CaseStyle>,
+    /// Whether to split words on letter <-> digit boundaries (`v3Point` ->
+    /// `v_3_point`) when a style above falls through to `to_snake_case`.
+    pub split_digits: bool,
+    validator: NamingValidator,
+}
+
+impl NamingConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn struct_ident(
+        &mut self,
+        scope: DefId,
+        file: FileId,
+        def: DefId,
+        name: &FastStr,
+    ) -> FastStr {
+        self.check(scope, file, def, IdentRole::Type, self.struct_style, name)
+    }
+
+    pub fn enum_ident(
+        &mut self,
+        scope: DefId,
+        file: FileId,
+        def: DefId,
+        name: &FastStr,
+    ) -> FastStr {
+        self.check(scope, file, def, IdentRole::Type, self.enum_style, name)
+    }
+
+    pub fn variant_ident(
+        &mut self,
+        scope: DefId,
+        file: FileId,
+        def: DefId,
+        name: &FastStr,
+    ) -> FastStr {
+        self.check(
+            scope,
+            file,
+            def,
+            IdentRole::Variant,
+            self.variant_style,
+            name,
+        )
+    }
+
+    pub fn field_ident(
+        &mut self,
+        scope: DefId,
+        file: FileId,
+        def: DefId,
+        name: &FastStr,
+    ) -> FastStr {
+        self.check(
+            scope,
+            file,
+            def,
+            IdentRole::FieldOrFn,
+            self.field_style,
+            name,
+        )
+    }
+
+    pub fn const_ident(
+        &mut self,
+        scope: DefId,
+        file: FileId,
+        def: DefId,
+        name: &FastStr,
+    ) -> FastStr {
+        self.check(scope, file, def, IdentRole::Const, self.const_style, name)
+    }
+
+    /// Every naming diagnostic collected so far across all idents checked
+    /// through this config, to print as warnings or fail the build on.
+    pub fn diagnostics(&self) -> &[NamingDiagnostic] {
+        self.validator.diagnostics()
+    }
+
+    fn check(
+        &mut self,
+        scope: DefId,
+        file: FileId,
+        def: DefId,
+        role: IdentRole,
+        style_override: Option<CaseStyle>,
+        name: &FastStr,
+    ) -> FastStr {
+        let style = style_override.unwrap_or_else(|| role.default_style());
+        self.validator
+            .check(scope, file, def, role, style, self.split_digits, name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_styles_match_the_historical_scheme() {
+        let mut cfg = NamingConfig::new();
+        let scope = DefId::from(0u32);
+        let file = FileId::from(0u32);
+        assert_eq!(
+            cfg.struct_ident(scope, file, DefId::from(1u32), &"foo_bar".into()),
+            "FooBar"
+        );
+        assert_eq!(
+            cfg.field_ident(scope, file, DefId::from(2u32), &"FooBar".into()),
+            "foo_bar"
+        );
+        assert_eq!(
+            cfg.const_ident(scope, file, DefId::from(3u32), &"foo_bar".into()),
+            "FOO_BAR"
+        );
+    }
+
+    #[test]
+    fn override_picks_the_configured_style() {
+        let mut cfg = NamingConfig::new();
+        cfg.field_style = Some(CaseStyle::KebabCase);
+        let scope = DefId::from(0u32);
+        let file = FileId::from(0u32);
+        assert_eq!(
+            cfg.field_ident(scope, file, DefId::from(1u32), &"FooBar".into()),
+            "foo-bar"
+        );
+    }
+
+    #[test]
+    fn split_digits_routes_through_to_field_ident() {
+        let mut cfg = NamingConfig::new();
+        cfg.split_digits = true;
+        let scope = DefId::from(0u32);
+        let file = FileId::from(0u32);
+        assert_eq!(
+            cfg.field_ident(scope, file, DefId::from(1u32), &"Utf8".into()),
+            "utf_8"
+        );
+    }
+}
@@ -1,7 +1,7 @@
-use std::{fmt::Display, ops::Deref};
+use std::{collections::HashMap, fmt::Display, ops::Deref};
 
 use faststr::FastStr;
-use heck::{ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToTitleCase, ToUpperCamelCase};
 use phf::phf_set;
 
 crate::newtype_index! {
@@ -71,6 +71,12 @@ lazy_static::lazy_static! {
         "await",
         "try"
     ];
+
+    // Subset of `KEYWORDS_SET` (plus the `_` wildcard placeholder) that isn't
+    // a valid raw identifier, e.g. `r#crate`/`r#self`/`r#Self` don't compile.
+    // These fall back to a trailing underscore instead.
+    static ref NON_RAW_KEYWORDS_SET: phf::Set<&'static str> =
+        phf_set!["crate", "self", "super", "Self", "_"];
 }
 
 #[derive(Hash, PartialEq, Eq, Clone, Debug, PartialOrd, Ord)]
@@ -101,10 +107,9 @@ where
 
 impl std::fmt::Display for Symbol {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        if &**self == "Self" {
-            return write!(f, "Self_");
-        }
-        if KEYWORDS_SET.contains(self) {
+        if NON_RAW_KEYWORDS_SET.contains(self) {
+            write!(f, "{}_", &**self)
+        } else if KEYWORDS_SET.contains(self) {
             write!(f, "r#{}", &**self)
         } else {
             write!(f, "{}", &**self)
@@ -112,6 +117,14 @@ impl std::fmt::Display for Symbol {
     }
 }
 
+/// Whether `name` needs keyword-escaping (`r#...`) or a trailing-underscore
+/// fallback to be used as a Rust identifier. Exposed so other subsystems,
+/// such as the naming-convention validator, can report on it without
+/// duplicating `KEYWORDS_SET`/`NON_RAW_KEYWORDS_SET`.
+pub(crate) fn is_keyword_escaped(name: &str) -> bool {
+    NON_RAW_KEYWORDS_SET.contains(name) || KEYWORDS_SET.contains(name)
+}
+
 #[derive(Hash, PartialEq, Eq, Clone, Debug, Copy)]
 pub enum EnumRepr {
     I32,
@@ -153,6 +166,81 @@ where
     }
 }
 
+/// Deduplicates identifiers produced within a single scope (a struct, enum or
+/// module), since lossy conversions like `snake_ident`/`upper_camel_ident`
+/// can make distinct IDL names collapse onto the same Rust identifier (e.g.
+/// `fooBar` and `foo_bar` both becoming `foo_bar`). Create one renamer per
+/// scope and feed it every identifier generated in that scope, in order.
+///
+/// On a collision, a numeric suffix is appended deterministically based on
+/// insertion order (`foo_bar`, `foo_bar2`, `foo_bar3`, ...), so generated
+/// code doesn't churn between runs.
+#[derive(Debug, Default, Clone)]
+pub struct SymbolRenamer {
+    // Last suffix tried for a given source name, so repeated dedup() calls
+    // for the same name don't restart the search from 2 every time.
+    next_suffix: HashMap<FastStr, u32>,
+    // Every identifier already handed out in this scope, so a suffixed
+    // candidate that happens to collide with a *real* name (or with another
+    // name's suffixed output) is skipped instead of handed out twice.
+    emitted: std::collections::HashSet<FastStr>,
+}
+
+impl SymbolRenamer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records `name` as generated within this scope, returning the
+    /// deduplicated [`Symbol`] to actually emit alongside the original
+    /// `name`, so callers can still emit a rename/alias for (de)serialization
+    /// when the two differ.
+    pub fn dedup(&mut self, name: FastStr) -> (Symbol, FastStr) {
+        let renamed = if self.emitted.insert(name.clone()) {
+            name.clone()
+        } else {
+            loop {
+                let suffix = self.next_suffix.entry(name.clone()).or_insert(1);
+                *suffix += 1;
+                let candidate = FastStr::from(format!("{name}{suffix}"));
+                if self.emitted.insert(candidate.clone()) {
+                    break candidate;
+                }
+            }
+        };
+        (Symbol(renamed), name)
+    }
+}
+
+/// The case convention used to render an identifier, mirroring the full set
+/// of conversions `heck`/`strum` expose. A per-generation config can pick one
+/// of these to override the default style [`IdentName`] otherwise applies to
+/// struct/enum/field/variant/const idents, so generated code can match the
+/// casing conventions of the source IDL or of an existing hand-written API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CaseStyle {
+    /// `lowerCamelCase`
+    CamelCase,
+    /// `UpperCamelCase`
+    PascalCase,
+    /// `kebab-case`
+    KebabCase,
+    /// `snake_case`
+    SnakeCase,
+    /// `SHOUTY_SNAKE_CASE`
+    ShoutySnakeCase,
+    /// `SCREAMING-KEBAB-CASE`
+    ScreamingKebabCase,
+    /// `Title Case`
+    TitleCase,
+    /// `Train-Case`
+    TrainCase,
+    /// `lowercase`
+    LowerCase,
+    /// `UPPERCASE`
+    UpperCase,
+}
+
 pub trait IdentName {
     fn struct_ident(&self) -> FastStr {
         self.upper_camel_ident()
@@ -190,6 +278,13 @@ pub trait IdentName {
     fn upper_camel_ident(&self) -> FastStr;
     fn snake_ident(&self, nonstandard: bool) -> FastStr;
     fn shouty_snake_case(&self, nonstandard: bool) -> FastStr;
+
+    /// Renders this name in an arbitrary [`CaseStyle`], for callers whose
+    /// config overrides the fixed UpperCamel/snake/SHOUTY scheme the other
+    /// methods on this trait hard-code. `split_digits` additionally breaks
+    /// words on letter <-> digit boundaries (e.g. `v3Point` -> `v_3_point`
+    /// rather than `v3point`); see `split_words` for why it defaults off.
+    fn rename(&self, style: CaseStyle, split_digits: bool) -> FastStr;
 }
 
 impl IdentName for &str {
@@ -200,7 +295,7 @@ impl IdentName for &str {
 
     fn snake_ident(&self, nonstandard: bool) -> FastStr {
         if nonstandard {
-            to_snake_case(self)
+            to_snake_case(self, false)
         } else {
             self.to_snake_case()
         }
@@ -209,12 +304,16 @@ impl IdentName for &str {
 
     fn shouty_snake_case(&self, nonstandard: bool) -> FastStr {
         if nonstandard {
-            to_snake_case(self).to_uppercase()
+            to_snake_case(self, false).to_uppercase()
         } else {
             self.to_shouty_snake_case()
         }
         .into()
     }
+
+    fn rename(&self, style: CaseStyle, split_digits: bool) -> FastStr {
+        rename_with_style(self, style, split_digits).into()
+    }
 }
 
 impl IdentName for FastStr {
@@ -229,37 +328,133 @@ impl IdentName for FastStr {
     fn shouty_snake_case(&self, nonstandard: bool) -> FastStr {
         (&**self).shouty_snake_case(nonstandard)
     }
+
+    fn rename(&self, style: CaseStyle, split_digits: bool) -> FastStr {
+        (&**self).rename(style, split_digits)
+    }
+}
+
+/// Renders `s` as `style`. The acronym-aware word splitting [`to_snake_case`]
+/// already does for `snake_ident`/`shouty_snake_case` is reused for the
+/// kebab/train/screaming-kebab variants so they split words the same way
+/// instead of falling back to `heck`'s naive case-transition splitting.
+///
+/// `split_digits` only affects the styles above that are derived from
+/// [`to_snake_case`] (kebab/snake/shouty-snake/screaming-kebab/train); the
+/// `CamelCase`/`PascalCase`/`TitleCase`/`LowerCase`/`UpperCase` arms go
+/// through `heck` or a plain case conversion and never split on letter <->
+/// digit boundaries, so the flag has no effect on them (e.g. `Utf8Reader`
+/// stays `Utf8Reader` under `PascalCase` regardless of `split_digits`).
+fn rename_with_style(s: &str, style: CaseStyle, split_digits: bool) -> String {
+    match style {
+        CaseStyle::CamelCase => s.to_lower_camel_case(),
+        CaseStyle::PascalCase => s.to_upper_camel_case(),
+        CaseStyle::KebabCase => to_snake_case(s, split_digits).replace('_', "-"),
+        CaseStyle::SnakeCase => to_snake_case(s, split_digits),
+        CaseStyle::ShoutySnakeCase => to_snake_case(s, split_digits).to_uppercase(),
+        CaseStyle::ScreamingKebabCase => to_snake_case(s, split_digits)
+            .to_uppercase()
+            .replace('_', "-"),
+        CaseStyle::TitleCase => s.to_title_case(),
+        CaseStyle::TrainCase => to_snake_case(s, split_digits)
+            .split('_')
+            .map(|word| {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                    None => String::new(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("-"),
+        CaseStyle::LowerCase => s.to_lowercase(),
+        CaseStyle::UpperCase => s.to_uppercase(),
+    }
 }
 
-// Taken from rustc.
-fn to_snake_case(mut str: &str) -> String {
-    let mut words = vec![];
-    // Preserve leading underscores
-    str = str.trim_start_matches(|c: char| {
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CharKind {
+    Lower,
+    Upper,
+    Digit,
+    Other,
+}
+
+fn char_kind(c: char) -> CharKind {
+    if c.is_lowercase() {
+        CharKind::Lower
+    } else if c.is_uppercase() {
+        CharKind::Upper
+    } else if c.is_ascii_digit() {
+        CharKind::Digit
+    } else {
+        CharKind::Other
+    }
+}
+
+/// Splits `s` into snake_case words, tracking the previous char's category
+/// (lower/upper/digit/other) and inserting a word boundary when it moves
+/// lower -> upper, or upper -> upper immediately followed by a lowercase
+/// word of two or more letters (the end of an acronym run, e.g.
+/// `HTTPServer` -> `HTTP` | `Server`, while `IDs` stays a single word since
+/// only one lowercase letter trails the acronym). Set `split_digits` to also
+/// break on letter <-> digit boundaries (e.g. `v3Point` -> `v` | `3` |
+/// `Point`); it's off by default since `Utf8` reads better as one word than
+/// `utf_8`. Each leading underscore is preserved as its own empty leading
+/// word, matching the original rustc-derived behavior.
+fn split_words(s: &str, split_digits: bool) -> Vec<String> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut prev_kind = None;
+    // Whether we're still in the run of leading underscores, so each one of
+    // `__foo`'s two leading underscores is preserved as its own empty word
+    // instead of only the first.
+    let mut leading = true;
+
+    for i in 0..chars.len() {
+        let c = chars[i];
         if c == '_' {
-            words.push(String::new());
-            true
-        } else {
-            false
-        }
-    });
-    for s in str.split('_') {
-        let mut last_upper = false;
-        let mut buf = String::new();
-        if s.is_empty() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            } else if leading {
+                words.push(String::new());
+            }
+            prev_kind = None;
             continue;
         }
-        for ch in s.chars() {
-            if !buf.is_empty() && buf != "'" && ch.is_uppercase() && !last_upper {
-                words.push(buf);
-                buf = String::new();
+        leading = false;
+
+        let kind = char_kind(c);
+        let boundary = match (prev_kind, kind) {
+            (Some(CharKind::Lower), CharKind::Upper) => true,
+            (Some(CharKind::Upper), CharKind::Upper) => {
+                chars.get(i + 1).is_some_and(|c| c.is_lowercase())
+                    && chars.get(i + 2).is_some_and(|c| c.is_lowercase())
             }
-            last_upper = ch.is_uppercase();
-            buf.extend(ch.to_lowercase());
+            (Some(CharKind::Lower), CharKind::Digit)
+            | (Some(CharKind::Digit), CharKind::Lower)
+            | (Some(CharKind::Upper), CharKind::Digit)
+            | (Some(CharKind::Digit), CharKind::Upper) => split_digits,
+            _ => false,
+        };
+
+        if boundary && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
         }
-        words.push(buf);
+        word.extend(c.to_lowercase());
+        prev_kind = Some(kind);
+    }
+    if !word.is_empty() {
+        words.push(word);
     }
-    words.join("_")
+    words
+}
+
+// Taken from rustc, reworked along the lines of rust-analyzer's `stdx`
+// case converter to track char categories instead of only letter case.
+fn to_snake_case(str: &str, split_digits: bool) -> String {
+    split_words(str, split_digits).join("_")
 }
 
 #[cfg(test)]
@@ -271,6 +466,47 @@ mod tests {
     #[test]
     fn snake_case() {
         assert_eq!("IDs".to_snake_case(), "i_ds");
-        assert_eq!(to_snake_case("IDs"), "ids");
+        assert_eq!(to_snake_case("IDs", false), "ids");
+        assert_eq!(to_snake_case("Utf8", false), "utf8");
+        assert_eq!(to_snake_case("HTTPServer", false), "http_server");
+        assert_eq!(to_snake_case("parseXMLHttp", false), "parse_xml_http");
+    }
+
+    #[test]
+    fn snake_case_split_digits() {
+        assert_eq!(to_snake_case("Utf8", true), "utf_8");
+        assert_eq!(to_snake_case("HTTP2Stream", false), "http2stream");
+        assert_eq!(to_snake_case("HTTP2Stream", true), "http_2_stream");
+        assert_eq!(to_snake_case("v3Point", true), "v_3_point");
+    }
+
+    #[test]
+    fn snake_case_preserves_each_leading_underscore() {
+        assert_eq!(to_snake_case("__foo", false), "__foo");
+        assert_eq!(to_snake_case("_foo", false), "_foo");
+    }
+
+    #[test]
+    fn symbol_renamer_dedups_collisions_in_order() {
+        use crate::symbol::SymbolRenamer;
+
+        let mut renamer = SymbolRenamer::new();
+        assert_eq!(renamer.dedup("foo_bar".into()).0 .0, "foo_bar");
+        assert_eq!(renamer.dedup("foo_bar".into()).0 .0, "foo_bar2");
+        assert_eq!(renamer.dedup("foo_bar".into()).0 .0, "foo_bar3");
+        assert_eq!(renamer.dedup("baz".into()).0 .0, "baz");
+    }
+
+    #[test]
+    fn symbol_renamer_skips_candidates_already_taken() {
+        use crate::symbol::SymbolRenamer;
+
+        // `fooBar` and `foo_bar` both snake_case down to "foo_bar", and a
+        // third, unrelated name happens to already be "foo_bar2".
+        let mut renamer = SymbolRenamer::new();
+        assert_eq!(renamer.dedup("foo_bar".into()).0 .0, "foo_bar");
+        assert_eq!(renamer.dedup("foo_bar".into()).0 .0, "foo_bar2");
+        let third = renamer.dedup("foo_bar2".into()).0 .0;
+        assert_ne!(third, "foo_bar2");
     }
 }
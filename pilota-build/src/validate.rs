@@ -0,0 +1,289 @@
+//! Naming-convention validation pass.
+//!
+//! Ports the spirit of rustc's `nonstandard_style` lints into pilota: before
+//! codegen, every collected [`Ident`] is checked against the casing its role
+//! (type/variant/trait, field/fn/mod, const) should have, and a
+//! [`NamingDiagnostic`] is recorded whenever the generated identifier
+//! surprises the user — it was renamed from the source name, it collided
+//! with another name already used in the same scope, or it became empty or
+//! had to be keyword-escaped. Callers can print the resulting report as
+//! warnings or turn it into a hard failure.
+
+use std::collections::HashMap;
+
+use faststr::FastStr;
+
+use crate::symbol::{CaseStyle, DefId, FileId, IdentName, SymbolRenamer};
+
+/// The syntactic role an [`Ident`] will fill in generated code, and thus the
+/// [`CaseStyle`] it's expected to be rendered in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentRole {
+    /// A struct, enum, or trait name.
+    Type,
+    /// An enum variant name.
+    Variant,
+    /// A field, function, or module name.
+    FieldOrFn,
+    /// A const name.
+    Const,
+}
+
+impl IdentRole {
+    /// The [`CaseStyle`] this role is rendered in absent a config override.
+    pub fn default_style(self) -> CaseStyle {
+        match self {
+            IdentRole::Type | IdentRole::Variant => CaseStyle::PascalCase,
+            IdentRole::FieldOrFn => CaseStyle::SnakeCase,
+            IdentRole::Const => CaseStyle::ShoutySnakeCase,
+        }
+    }
+}
+
+/// What was surprising about a generated identifier, and the names involved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NamingDiagnosticKind {
+    /// The generated identifier differs from the source name.
+    Renamed { from: FastStr, to: FastStr },
+    /// The generated identifier collided with one already used in the same
+    /// scope and was deduplicated with a numeric suffix.
+    Collision { from: FastStr, to: FastStr },
+    /// The generated identifier needed keyword-escaping (`r#...` or a
+    /// trailing underscore) to be a valid Rust identifier.
+    KeywordEscaped { from: FastStr, to: FastStr },
+    /// The source name became empty after conversion.
+    Empty { from: FastStr },
+}
+
+/// A single reported rename, keyed by the file and definition it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamingDiagnostic {
+    pub file: FileId,
+    pub def: DefId,
+    pub role: IdentRole,
+    pub kind: NamingDiagnosticKind,
+}
+
+/// Runs the naming-convention validation pass over a stream of collected
+/// idents, recording a [`NamingDiagnostic`] for every automatic rename
+/// pilota applies. One `scope` id (the enclosing struct/enum/module's
+/// [`DefId`]) should be used for every ident declared within it, so
+/// collisions are only flagged against siblings, not the whole program.
+#[derive(Debug, Default)]
+pub struct NamingValidator {
+    scopes: HashMap<DefId, SymbolRenamer>,
+    diagnostics: Vec<NamingDiagnostic>,
+}
+
+impl NamingValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Validates `source_name` for `role` within `scope`, rendering it with
+    /// `style` (typically `role.default_style()`, unless a generation config
+    /// overrode it) and returning the final identifier text to actually
+    /// emit — already keyword-escaped, matching what `Symbol`'s `Display`
+    /// impl would print for it.
+    pub fn check(
+        &mut self,
+        scope: DefId,
+        file: FileId,
+        def: DefId,
+        role: IdentRole,
+        style: CaseStyle,
+        split_digits: bool,
+        source_name: &FastStr,
+    ) -> FastStr {
+        let expected = source_name.rename(style, split_digits);
+        let (symbol, _) = self
+            .scopes
+            .entry(scope)
+            .or_default()
+            .dedup(expected.clone());
+        let result: FastStr = symbol.0.clone();
+        // The text actually emitted by codegen: `Symbol`'s `Display` escapes
+        // keywords (`r#...` or a trailing underscore) at print time, so the
+        // diagnostics and the returned ident must go through it too, or a
+        // `KeywordEscaped` report would show the un-escaped source word.
+        let emitted: FastStr = symbol.to_string().into();
+
+        if result.is_empty() {
+            self.diagnostics.push(NamingDiagnostic {
+                file,
+                def,
+                role,
+                kind: NamingDiagnosticKind::Empty {
+                    from: source_name.clone(),
+                },
+            });
+        } else if result != expected {
+            self.diagnostics.push(NamingDiagnostic {
+                file,
+                def,
+                role,
+                kind: NamingDiagnosticKind::Collision {
+                    from: source_name.clone(),
+                    to: emitted.clone(),
+                },
+            });
+        } else if result.as_str() != source_name.as_str() {
+            self.diagnostics.push(NamingDiagnostic {
+                file,
+                def,
+                role,
+                kind: NamingDiagnosticKind::Renamed {
+                    from: source_name.clone(),
+                    to: emitted.clone(),
+                },
+            });
+        }
+
+        if crate::symbol::is_keyword_escaped(&result) {
+            self.diagnostics.push(NamingDiagnostic {
+                file,
+                def,
+                role,
+                kind: NamingDiagnosticKind::KeywordEscaped {
+                    from: source_name.clone(),
+                    to: emitted.clone(),
+                },
+            });
+        }
+
+        emitted
+    }
+
+    pub fn diagnostics(&self) -> &[NamingDiagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn into_diagnostics(self) -> Vec<NamingDiagnostic> {
+        self.diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renamed_fires_when_the_source_name_does_not_match_its_style() {
+        let mut validator = NamingValidator::new();
+        let scope = DefId::from(0u32);
+        let file = FileId::from(0u32);
+        let result = validator.check(
+            scope,
+            file,
+            DefId::from(1u32),
+            IdentRole::Type,
+            CaseStyle::PascalCase,
+            false,
+            &"data_v1".into(),
+        );
+        assert_eq!(result, "DataV1");
+        assert_eq!(
+            validator.diagnostics(),
+            &[NamingDiagnostic {
+                file,
+                def: DefId::from(1u32),
+                role: IdentRole::Type,
+                kind: NamingDiagnosticKind::Renamed {
+                    from: "data_v1".into(),
+                    to: "DataV1".into(),
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn collision_fires_when_two_names_normalize_to_the_same_ident() {
+        let mut validator = NamingValidator::new();
+        let scope = DefId::from(0u32);
+        let file = FileId::from(0u32);
+        let first = validator.check(
+            scope,
+            file,
+            DefId::from(1u32),
+            IdentRole::Type,
+            CaseStyle::PascalCase,
+            false,
+            &"DataV1".into(),
+        );
+        let second = validator.check(
+            scope,
+            file,
+            DefId::from(2u32),
+            IdentRole::Type,
+            CaseStyle::PascalCase,
+            false,
+            &"data_v1".into(),
+        );
+        assert_eq!(first, "DataV1");
+        assert_eq!(second, "DataV12");
+        assert!(validator.diagnostics().iter().any(|d| d.def
+            == DefId::from(2u32)
+            && d.kind
+                == NamingDiagnosticKind::Collision {
+                    from: "data_v1".into(),
+                    to: "DataV12".into(),
+                }));
+    }
+
+    #[test]
+    fn keyword_escaped_fires_and_reports_the_escaped_ident() {
+        let mut validator = NamingValidator::new();
+        let scope = DefId::from(0u32);
+        let file = FileId::from(0u32);
+        let result = validator.check(
+            scope,
+            file,
+            DefId::from(1u32),
+            IdentRole::FieldOrFn,
+            CaseStyle::SnakeCase,
+            false,
+            &"type".into(),
+        );
+        assert_eq!(result, "r#type");
+        assert_eq!(
+            validator.diagnostics(),
+            &[NamingDiagnostic {
+                file,
+                def: DefId::from(1u32),
+                role: IdentRole::FieldOrFn,
+                kind: NamingDiagnosticKind::KeywordEscaped {
+                    from: "type".into(),
+                    to: "r#type".into(),
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn empty_fires_when_the_source_name_converts_to_nothing() {
+        let mut validator = NamingValidator::new();
+        let scope = DefId::from(0u32);
+        let file = FileId::from(0u32);
+        let result = validator.check(
+            scope,
+            file,
+            DefId::from(1u32),
+            IdentRole::FieldOrFn,
+            CaseStyle::SnakeCase,
+            false,
+            &"_".into(),
+        );
+        assert_eq!(result, "");
+        assert_eq!(
+            validator.diagnostics(),
+            &[NamingDiagnostic {
+                file,
+                def: DefId::from(1u32),
+                role: IdentRole::FieldOrFn,
+                kind: NamingDiagnosticKind::Empty {
+                    from: "_".into(),
+                },
+            }]
+        );
+    }
+}